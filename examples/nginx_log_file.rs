@@ -2,19 +2,15 @@ use std::{
     fs::File,
     io::{BufRead, BufReader},
     net::IpAddr,
+    path::Path,
     str::FromStr,
 };
 
 use anyhow::{anyhow, Result};
 use chrono::{DateTime, Utc};
-use polars::{
-    io::SerReader,
-    prelude::{CsvReadOptions, ParquetWriter},
-};
+use polars::prelude::*;
 use regex::Regex;
-use serde::Serialize;
 
-#[derive(Debug, Serialize)]
 struct NginxLog {
     addr: IpAddr,
     datetime: DateTime<Utc>,
@@ -32,24 +28,89 @@ fn main() -> Result<()> {
     let file = File::open(file)?;
     let reader = BufReader::new(file);
 
-    let csv_file = std::fs::File::create("fixtures/nginx_access.csv")?;
-    let mut csv_writer = csv::Writer::from_writer(csv_file);
-    for line in reader.lines().map_while(Result::ok) {
-        let log = parse_nginx_log(&line).map_err(|e| anyhow!("Failed to parse log: {:?}", e))?;
-        csv_writer.serialize(log)?;
-        csv_writer.flush()?;
+    logs_to_parquet(reader, Path::new("./fixtures/nginx_access.parquet"), 10_000)
+}
+
+/// Parses nginx access log lines from `reader` straight into typed Polars
+/// columns and writes them to a Parquet file at `out`, accumulating at most
+/// `batch_size` rows in memory at a time. This replaces the old
+/// parse-to-CSV-then-read-CSV-back pipeline, so columns keep their real
+/// types (a `Datetime`, `u16`/`u64` counters) instead of everything
+/// round-tripping through CSV strings. IP addresses are stored as strings.
+fn logs_to_parquet(reader: impl BufRead, out: &Path, batch_size: usize) -> Result<()> {
+    let mut file = Some(File::create(out)?);
+    let mut writer: Option<BatchedWriter<File>> = None;
+    let mut batch: Vec<NginxLog> = Vec::with_capacity(batch_size);
+
+    for line in reader.lines() {
+        let line = line?;
+        batch.push(parse_nginx_log(&line).map_err(|e| anyhow!("Failed to parse log: {:?}", e))?);
+
+        if batch.len() == batch_size {
+            flush_batch(&mut batch, &mut writer, &mut file)?;
+        }
     }
+    flush_batch(&mut batch, &mut writer, &mut file)?;
 
-    let mut df = CsvReadOptions::default()
-        .try_into_reader_with_file_path(Some("fixtures/nginx_access.csv".into()))?
-        .finish()?;
+    match writer {
+        Some(mut writer) => writer.finish()?,
+        // No rows were ever read; still produce an (empty) Parquet file.
+        None => ParquetWriter::new(file.take().unwrap()).finish(&mut nginx_logs_to_df(&[])?)?,
+    };
 
-    let mut file = std::fs::File::create("./fixtures/nginx_access.parquet")?;
-    ParquetWriter::new(&mut file).finish(&mut df).unwrap();
+    Ok(())
+}
+
+/// Writes `batch` as one Parquet row group, lazily opening the batched
+/// writer (which needs a schema, so it can only be created once the first
+/// batch's `DataFrame` exists) and leaving it open for later batches.
+fn flush_batch(
+    batch: &mut Vec<NginxLog>,
+    writer: &mut Option<BatchedWriter<File>>,
+    file: &mut Option<File>,
+) -> Result<()> {
+    if batch.is_empty() {
+        return Ok(());
+    }
+
+    let df = nginx_logs_to_df(batch)?;
+    if writer.is_none() {
+        *writer = Some(ParquetWriter::new(file.take().unwrap()).batched(&df.schema())?);
+    }
+    writer.as_mut().unwrap().write_batch(&df)?;
+    batch.clear();
 
     Ok(())
 }
 
+fn nginx_logs_to_df(logs: &[NginxLog]) -> Result<DataFrame> {
+    let addr: Vec<String> = logs.iter().map(|l| l.addr.to_string()).collect();
+    let method: Vec<&str> = logs.iter().map(|l| l.method.as_str()).collect();
+    let url: Vec<&str> = logs.iter().map(|l| l.url.as_str()).collect();
+    let protocol: Vec<&str> = logs.iter().map(|l| l.protocol.as_str()).collect();
+    let status: Vec<u16> = logs.iter().map(|l| l.status).collect();
+    let body_bytes: Vec<u64> = logs.iter().map(|l| l.body_bytes).collect();
+    let referer: Vec<&str> = logs.iter().map(|l| l.referer.as_str()).collect();
+    let user_agent: Vec<&str> = logs.iter().map(|l| l.user_agent.as_str()).collect();
+    let datetime: Vec<i64> = logs.iter().map(|l| l.datetime.timestamp_millis()).collect();
+
+    let mut df = df!(
+        "addr" => addr,
+        "method" => method,
+        "url" => url,
+        "protocol" => protocol,
+        "status" => status,
+        "body_bytes" => body_bytes,
+        "referer" => referer,
+        "user_agent" => user_agent,
+    )?;
+    df.with_column(
+        Series::new("datetime", datetime).cast(&DataType::Datetime(TimeUnit::Milliseconds, None))?,
+    )?;
+
+    Ok(df)
+}
+
 fn parse_nginx_log(s: &str) -> Result<NginxLog> {
     let re = Regex::new(
         r#"^(?<ip>\S+)\s+\S+\s+\S+\s+\[(?<date>[^\]]+)\]\s+"(?<method>\S+)\s+(?<url>\S+)\s+(?<proto>[^"]+)"\s+(?<status>\d+)\s+(?<bytes>\d+)\s+"(?<referer>[^"]+)"\s+"(?<ua>[^"]+)"$"#,
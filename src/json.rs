@@ -1,23 +1,33 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt};
 
-use anyhow::{anyhow, Result};
+use anyhow::Result;
 use winnow::{
-    ascii::{digit1, multispace0},
-    combinator::{alt, delimited, opt, separated, separated_pair, trace},
-    error::{ContextError, ErrMode, ParserError},
-    stream::{AsChar, Stream, StreamIsPartial},
-    token::take_until,
-    PResult, Parser,
+    ascii::{digit1, line_ending, multispace0, space0},
+    combinator::{
+        alt, cut_err, delimited, opt, preceded, repeat, separated, separated_pair, terminated,
+        trace,
+    },
+    error::{ContextError, ErrMode},
+    stream::{Compare, StreamIsPartial},
+    token::{take, take_till},
+    PResult, Parser, Partial,
 };
 
+/// Error type threaded through every parser: a [`ContextError`] whose labels
+/// are the plain `&'static str`s passed to `.context(...)`, e.g. `"object
+/// key"` or `"number"`. [`JsonParseError`] turns the innermost couple of
+/// these, plus the failure position, into a human-readable message.
+pub type Error = ContextError<&'static str>;
+
 #[derive(Debug, Clone, PartialEq)]
-enum Num {
+pub enum Num {
     Int(i64),
+    UInt(u64),
     Float(f64),
 }
 
 #[derive(Debug, Clone, PartialEq)]
-enum JsonValue {
+pub enum JsonValue {
     Null,
     Bool(bool),
     Number(Num),
@@ -26,6 +36,44 @@ enum JsonValue {
     Object(HashMap<String, JsonValue>),
 }
 
+/// Input accepted by every parser below: either a complete `&str` (used by
+/// [`parse_json`]) or a [`Stream`] that may still be growing (used by
+/// [`parse_ndjson`]).
+trait JsonInput<'i>:
+    winnow::stream::Stream<Token = char, Slice = &'i str>
+    + StreamIsPartial
+    + Compare<&'static str>
+    + Compare<char>
+    + Clone
+{
+    /// Whitespace allowed around structural punctuation (`{ } [ ] , :`).
+    fn token_ws(input: &mut Self) -> PResult<(), Error>;
+}
+
+impl<'i> JsonInput<'i> for &'i str {
+    fn token_ws(input: &mut Self) -> PResult<(), Error> {
+        multispace0.void().parse_next(input)
+    }
+}
+
+impl<'i> JsonInput<'i> for Partial<&'i str> {
+    // NDJSON reserves `\n` as the record terminator, so on a streaming input
+    // punctuation is only surrounded by horizontal whitespace: if this
+    // consumed `\n` like the complete-`&str` impl does, a record's closing
+    // `}`/`]` would eat the line ending `parse_ndjson` needs to see next,
+    // and the record would backtrack (or block on `Incomplete`) instead of
+    // ever matching.
+    fn token_ws(input: &mut Self) -> PResult<(), Error> {
+        space0.void().parse_next(input)
+    }
+}
+
+/// A possibly-incomplete stream of bytes from an NDJSON file being read in
+/// chunks, e.g. from a `BufReader`. Reaching the end of the buffer mid-record
+/// reports `ErrMode::Incomplete` instead of a hard parse error, so the caller
+/// can append more data and retry.
+pub type Stream<'i> = Partial<&'i str>;
+
 fn main() -> Result<()> {
     let s = r#"{
         "name": "John Doe",
@@ -44,103 +92,366 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-fn parse_json(input: &str) -> Result<JsonValue> {
-    let input = &mut (&*input);
-    parse_value(input).map_err(|e: ErrMode<ContextError>| anyhow!("Failed to parse JSON: {:?}", e))
+fn parse_json(input: &str) -> std::result::Result<JsonValue, JsonParseError> {
+    let mut remaining = input;
+    parse_value(&mut remaining).map_err(|e| JsonParseError::new(input, remaining, e))
+}
+
+/// A JSON parse failure, located precisely enough to point a user at the
+/// offending character: the byte offset and 1-based line/column it occurred
+/// at, a snippet of the input starting there, and the `.context(...)` labels
+/// active at that point (innermost label first).
+#[derive(Debug)]
+pub struct JsonParseError {
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
+    pub snippet: String,
+    pub context: Vec<&'static str>,
+}
+
+impl JsonParseError {
+    fn new(original: &str, remaining: &str, err: ErrMode<Error>) -> Self {
+        let offset = original.len() - remaining.len();
+        let consumed = &original[..offset];
+        let line = consumed.matches('\n').count() + 1;
+        let column = match consumed.rfind('\n') {
+            Some(last_newline) => offset - last_newline,
+            None => offset + 1,
+        };
+        let context = match &err {
+            ErrMode::Backtrack(e) | ErrMode::Cut(e) => e.context().copied().collect(),
+            ErrMode::Incomplete(_) => Vec::new(),
+        };
+        let snippet = remaining.chars().take(32).collect();
+
+        JsonParseError {
+            offset,
+            line,
+            column,
+            snippet,
+            context,
+        }
+    }
+}
+
+impl fmt::Display for JsonParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "error at line {}, col {}", self.line, self.column)?;
+        let mut labels = self.context.iter();
+        if let Some(expected) = labels.next() {
+            write!(f, ": expected {expected}")?;
+            if let Some(enclosing) = labels.next() {
+                write!(f, " while parsing {enclosing}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for JsonParseError {}
+
+/// Parses a single line of a newline-delimited JSON stream. Blank lines yield
+/// `None`; a JSON value followed by the line ending yields `Some(value)`. If
+/// the stream runs out before a full line is available, returns
+/// `ErrMode::Incomplete` so the caller can feed more input and retry.
+pub fn parse_ndjson<'i>(input: &mut Stream<'i>) -> PResult<Option<JsonValue>, Error> {
+    alt((
+        terminated(delimited(ws, parse_value, ws), line_ending).map(Some),
+        line_ending.value(None),
+    ))
+    .parse_next(input)
+}
+
+fn ws<'i, Input: JsonInput<'i>>(input: &mut Input) -> PResult<(), Error> {
+    // Only spaces/tabs, not `\n` or `\r`: `parse_ndjson` relies on the trailing
+    // `ws` leaving the record's line terminator for `line_ending` to consume.
+    space0.void().parse_next(input)
 }
 
-pub fn sep_with_space<Input, Output, Error, ParseNext>(
+fn sep_with_space<'i, Input, Output, ParseNext>(
     mut parser: ParseNext,
 ) -> impl Parser<Input, (), Error>
 where
-    Input: Stream + StreamIsPartial,
-    <Input as Stream>::Token: AsChar + Clone,
-    Error: ParserError<Input>,
+    Input: JsonInput<'i>,
     ParseNext: Parser<Input, Output, Error>,
 {
     trace("sep_with_space", move |input: &mut Input| {
-        let _ = multispace0.parse_next(input)?;
+        Input::token_ws(input)?;
         parser.parse_next(input)?;
-        multispace0.parse_next(input)?;
+        Input::token_ws(input)?;
         Ok(())
     })
 }
 
-fn parse_null(input: &mut &str) -> PResult<()> {
+fn parse_null<'i, Input: JsonInput<'i>>(input: &mut Input) -> PResult<(), Error> {
     "null".value(()).parse_next(input)
 }
 
-fn parse_bool(input: &mut &str) -> PResult<bool> {
+fn parse_bool<'i, Input: JsonInput<'i>>(input: &mut Input) -> PResult<bool, Error> {
     alt(("true", "false")).parse_to().parse_next(input)
 }
 
-fn parse_num(input: &mut &str) -> PResult<Num> {
-    let sign = opt(alt(("+", "-")))
-        .map(|s| s.is_some_and(|f| f == "-"))
+/// Captures the full numeric lexeme (sign, integer part, optional fraction,
+/// optional exponent) as a slice rather than reconstructing it piecewise, so
+/// large integers and fractions with leading zeros (`1.001`) parse exactly
+/// as written. Integers that overflow `i64` fall back to `Num::UInt`, and
+/// anything with a fraction or exponent goes through a single `f64` parse
+/// of the whole lexeme.
+fn parse_num<'i, Input: JsonInput<'i>>(input: &mut Input) -> PResult<Num, Error> {
+    let lexeme: &str = (
+        opt(alt(('+', '-'))),
+        digit1,
+        opt(('.', digit1)),
+        opt((alt(('e', 'E')), opt(alt(('+', '-'))), digit1)),
+    )
+        .take()
         .parse_next(input)?;
-    let num = digit1.parse_to::<i64>().parse_next(input)?;
-    let ret: Result<(), ErrMode<ContextError>> = ".".value(()).parse_next(input);
-    if ret.is_ok() {
-        let frac = digit1.parse_to::<i64>().parse_next(input)?;
-        let mut v = format!("{}.{}", num, frac).parse::<f64>().unwrap();
-
-        let e: Result<(), ErrMode<ContextError>> = alt(("e", "E")).value(()).parse_next(input);
-        if e.is_ok() {
-            let e_sign = opt(alt(("+", "-")))
-                .map(|s| s.is_some_and(|f| f == "-"))
-                .parse_next(input)?;
-            let exp: i64 = digit1.parse_to::<i64>().parse_next(input)?;
-            let exp = if e_sign { -exp } else { exp };
-            v *= 10f64.powi(exp as i32);
-        }
-        Ok(if sign { Num::Float(-v) } else { Num::Float(v) })
+
+    if lexeme.contains(['.', 'e', 'E']) {
+        Ok(Num::Float(lexeme.parse().unwrap()))
+    } else if let Ok(n) = lexeme.parse::<i64>() {
+        Ok(Num::Int(n))
+    } else if let Ok(n) = lexeme.parse::<u64>() {
+        Ok(Num::UInt(n))
     } else {
-        Ok(if sign { Num::Int(-num) } else { Num::Int(num) })
+        Ok(Num::Float(lexeme.parse().unwrap()))
     }
 }
 
-fn parse_string(input: &mut &str) -> PResult<String> {
-    let ret = delimited('"', take_until(0.., '"'), '"').parse_next(input)?;
-    PResult::Ok(ret.to_string())
+/// A chunk of a JSON string: either a run of characters copied verbatim, or
+/// a single character produced by decoding one `\X`/`\uXXXX` escape.
+enum StringFragment<'i> {
+    Literal(&'i str),
+    Escaped(char),
+}
+
+fn parse_string<'i, Input: JsonInput<'i>>(input: &mut Input) -> PResult<String, Error> {
+    let build_string = repeat(0.., parse_string_fragment).fold(String::new, |mut s, frag| {
+        match frag {
+            StringFragment::Literal(l) => s.push_str(l),
+            StringFragment::Escaped(c) => s.push(c),
+        }
+        s
+    });
+    delimited('"', build_string, '"').parse_next(input)
+}
+
+fn parse_string_fragment<'i, Input: JsonInput<'i>>(
+    input: &mut Input,
+) -> PResult<StringFragment<'i>, Error> {
+    alt((
+        take_till(1.., ('"', '\\')).map(StringFragment::Literal),
+        preceded('\\', parse_escaped_char).map(StringFragment::Escaped),
+    ))
+    .parse_next(input)
+}
+
+fn parse_escaped_char<'i, Input: JsonInput<'i>>(input: &mut Input) -> PResult<char, Error> {
+    alt((
+        '"'.value('"'),
+        '\\'.value('\\'),
+        '/'.value('/'),
+        'b'.value('\u{8}'),
+        'f'.value('\u{c}'),
+        'n'.value('\n'),
+        'r'.value('\r'),
+        't'.value('\t'),
+        preceded('u', parse_unicode_escape),
+    ))
+    .parse_next(input)
+}
+
+/// Parses the four hex digits following a bare `\u`, combining a high/low
+/// surrogate pair into the astral code point it encodes and rejecting lone
+/// surrogates.
+fn parse_unicode_escape<'i, Input: JsonInput<'i>>(input: &mut Input) -> PResult<char, Error> {
+    alt((
+        parse_hex4
+            .verify(|cp: &u16| !(0xD800..0xE000).contains(cp))
+            .map(|cp| cp as u32),
+        separated_pair(parse_hex4, "\\u", parse_hex4)
+            .verify(|(high, low): &(u16, u16)| {
+                (0xD800..0xDC00).contains(high) && (0xDC00..0xE000).contains(low)
+            })
+            .map(|(high, low)| 0x10000 + (high as u32 - 0xD800) * 0x400 + (low as u32 - 0xDC00)),
+    ))
+    .verify_map(char::from_u32)
+    .parse_next(input)
 }
 
-fn parse_array(input: &mut &str) -> PResult<Vec<JsonValue>> {
+fn parse_hex4<'i, Input: JsonInput<'i>>(input: &mut Input) -> PResult<u16, Error> {
+    take(4usize)
+        .try_map(|s: &str| u16::from_str_radix(s, 16))
+        .parse_next(input)
+}
+
+fn parse_array<'i, Input: JsonInput<'i>>(input: &mut Input) -> PResult<Vec<JsonValue>, Error> {
     let sep1 = sep_with_space('[');
-    let sep2 = sep_with_space(']');
+    let sep2 = sep_with_space(']').context("']'");
     let sep_comma = sep_with_space(',');
-    let parse_values = separated(0.., parse_value, sep_comma);
-    delimited(sep1, parse_values, sep2).parse_next(input)
+    let parse_values = separated(0.., parse_value.context("array element"), sep_comma);
+    preceded(sep1, cut_err(terminated(parse_values, sep2))).parse_next(input)
 }
 
-fn parse_object(input: &mut &str) -> PResult<HashMap<String, JsonValue>> {
+fn parse_object<'i, Input: JsonInput<'i>>(
+    input: &mut Input,
+) -> PResult<HashMap<String, JsonValue>, Error> {
     let sep1 = sep_with_space('{');
-    let sep2 = sep_with_space('}');
+    let sep2 = sep_with_space('}').context("'}'");
     let sep_comma = sep_with_space(',');
-    let sep_colon = sep_with_space(':');
+    let sep_colon = sep_with_space(':').context("':'");
 
-    let parse_kv_pair = separated_pair(parse_string, sep_colon, parse_value);
+    let parse_kv_pair = separated_pair(parse_string, cut_err(sep_colon), cut_err(parse_value))
+        .context("object key");
     let parse_kv = separated(1.., parse_kv_pair, sep_comma);
-    delimited(sep1, parse_kv, sep2).parse_next(input)
+    preceded(sep1, cut_err(terminated(parse_kv, sep2))).parse_next(input)
 }
 
-fn parse_value(input: &mut &str) -> PResult<JsonValue> {
+fn parse_value<'i, Input: JsonInput<'i>>(input: &mut Input) -> PResult<JsonValue, Error> {
     alt((
         parse_null.value(JsonValue::Null),
         parse_bool.map(JsonValue::Bool),
-        parse_num.map(JsonValue::Number),
-        parse_string.map(JsonValue::String),
+        parse_num.context("number").map(JsonValue::Number),
+        parse_string.context("string").map(JsonValue::String),
         parse_array.map(JsonValue::Array),
         parse_object.map(JsonValue::Object),
     ))
     .parse_next(input)
 }
 
+/// Renders a [`JsonValue`] back to compact JSON text, with no whitespace
+/// between tokens. `parse_json(&to_string(&v))` round-trips to `v` for
+/// finite numbers; a non-finite float (which `parse_json` yields for an
+/// out-of-range exponent such as `1e400`) renders as `inf.0`/`NaN.0`, which
+/// does not parse back.
+pub fn to_string(value: &JsonValue) -> String {
+    let mut out = String::new();
+    write_value(value, &mut out);
+    out
+}
+
+/// Renders a [`JsonValue`] back to JSON text, indenting nested arrays and
+/// objects by `indent` spaces per level with one element per line.
+pub fn to_string_pretty(value: &JsonValue, indent: usize) -> String {
+    let mut out = String::new();
+    write_value_pretty(value, indent, 0, &mut out);
+    out
+}
+
+fn write_value(value: &JsonValue, out: &mut String) {
+    match value {
+        JsonValue::Null => out.push_str("null"),
+        JsonValue::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        JsonValue::Number(n) => out.push_str(&format_number(n)),
+        JsonValue::String(s) => write_escaped_string(s, out),
+        JsonValue::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_value(item, out);
+            }
+            out.push(']');
+        }
+        JsonValue::Object(entries) => {
+            out.push('{');
+            for (i, (k, v)) in entries.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_escaped_string(k, out);
+                out.push(':');
+                write_value(v, out);
+            }
+            out.push('}');
+        }
+    }
+}
+
+fn write_value_pretty(value: &JsonValue, indent: usize, level: usize, out: &mut String) {
+    match value {
+        JsonValue::Array(items) if !items.is_empty() => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                out.push_str(if i == 0 { "\n" } else { ",\n" });
+                out.push_str(&" ".repeat(indent * (level + 1)));
+                write_value_pretty(item, indent, level + 1, out);
+            }
+            out.push('\n');
+            out.push_str(&" ".repeat(indent * level));
+            out.push(']');
+        }
+        JsonValue::Object(entries) if !entries.is_empty() => {
+            // `HashMap` has no iteration order of its own; sort by key so
+            // pretty output (and its tests) are deterministic.
+            let mut entries: Vec<_> = entries.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+
+            out.push('{');
+            for (i, (k, v)) in entries.iter().enumerate() {
+                out.push_str(if i == 0 { "\n" } else { ",\n" });
+                out.push_str(&" ".repeat(indent * (level + 1)));
+                write_escaped_string(k, out);
+                out.push_str(": ");
+                write_value_pretty(v, indent, level + 1, out);
+            }
+            out.push('\n');
+            out.push_str(&" ".repeat(indent * level));
+            out.push('}');
+        }
+        _ => write_value(value, out),
+    }
+}
+
+fn format_number(num: &Num) -> String {
+    match num {
+        Num::Int(n) => n.to_string(),
+        Num::UInt(n) => n.to_string(),
+        Num::Float(f) => {
+            let mut s = format!("{f}");
+            if !s.contains(['.', 'e', 'E']) {
+                s.push_str(".0");
+            }
+            s
+        }
+    }
+}
+
+fn write_escaped_string(s: &str, out: &mut String) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04X}", c as u32)),
+            c if c.is_ascii() => out.push(c),
+            c => {
+                let cp = c as u32;
+                if cp <= 0xFFFF {
+                    out.push_str(&format!("\\u{cp:04X}"));
+                } else {
+                    let cp = cp - 0x10000;
+                    let high = 0xD800 + (cp >> 10);
+                    let low = 0xDC00 + (cp & 0x3FF);
+                    out.push_str(&format!("\\u{high:04X}\\u{low:04X}"));
+                }
+            }
+        }
+    }
+    out.push('"');
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn test_parse_null() -> PResult<(), ContextError> {
+    fn test_parse_null() -> PResult<(), Error> {
         let input = &mut (r#"null"#);
         parse_null(input)?;
 
@@ -148,7 +459,7 @@ mod tests {
     }
 
     #[test]
-    fn test_parse_bool() -> PResult<(), ContextError> {
+    fn test_parse_bool() -> PResult<(), Error> {
         let input = &mut (r#"true"#);
         let ret = parse_bool(input)?;
         assert!(ret);
@@ -161,7 +472,7 @@ mod tests {
     }
 
     #[test]
-    fn test_parse_num() -> PResult<(), ContextError> {
+    fn test_parse_num() -> PResult<(), Error> {
         let input = "123";
         let result = parse_num(&mut (&*input))?;
         assert_eq!(result, Num::Int(123));
@@ -182,7 +493,29 @@ mod tests {
     }
 
     #[test]
-    fn test_parse_string() -> PResult<(), ContextError> {
+    fn test_parse_num_leading_zero_fraction() -> PResult<(), Error> {
+        let input = "1.001";
+        let result = parse_num(&mut (&*input))?;
+        assert_eq!(result, Num::Float(1.001));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_num_overflows_to_uint() -> PResult<(), Error> {
+        let input = "18446744073709551615";
+        let result = parse_num(&mut (&*input))?;
+        assert_eq!(result, Num::UInt(u64::MAX));
+
+        let input = "9223372036854775808";
+        let result = parse_num(&mut (&*input))?;
+        assert_eq!(result, Num::UInt(i64::MAX as u64 + 1));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_string() -> PResult<(), Error> {
         let input = &mut (r#""hello""#);
         let ret = parse_string(input)?;
         assert_eq!(ret, "hello");
@@ -191,7 +524,34 @@ mod tests {
     }
 
     #[test]
-    fn test_parse_array() -> PResult<(), ContextError> {
+    fn test_parse_string_escaped_quote() -> PResult<(), Error> {
+        let input = &mut (r#""a\"b""#);
+        let ret = parse_string(input)?;
+        assert_eq!(ret, "a\"b");
+
+        PResult::Ok(())
+    }
+
+    #[test]
+    fn test_parse_string_escape_sequences() -> PResult<(), Error> {
+        let input = &mut (r#""\n\t""#);
+        let ret = parse_string(input)?;
+        assert_eq!(ret, "\n\t");
+
+        PResult::Ok(())
+    }
+
+    #[test]
+    fn test_parse_string_surrogate_pair() -> PResult<(), Error> {
+        let input = &mut (r#""\uD83D\uDE00""#);
+        let ret = parse_string(input)?;
+        assert_eq!(ret, "\u{1F600}");
+
+        PResult::Ok(())
+    }
+
+    #[test]
+    fn test_parse_array() -> PResult<(), Error> {
         let input = r#"[1, 2, 3]"#;
         let result = parse_array(&mut (&*input))?;
 
@@ -218,7 +578,7 @@ mod tests {
     }
 
     #[test]
-    fn test_parse_science_notation() -> PResult<(), ContextError> {
+    fn test_parse_science_notation() -> PResult<(), Error> {
         let input = "1.23e4";
         let result = parse_num(&mut (&*input))?;
         assert_eq!(result, Num::Float(1.23e4));
@@ -235,7 +595,7 @@ mod tests {
     }
 
     #[test]
-    fn test_parse_object() -> PResult<(), ContextError> {
+    fn test_parse_object() -> PResult<(), Error> {
         let input = r#"{"a": 1, "b": 2}"#;
         let result = parse_object(&mut (&*input))?;
         let mut expected = HashMap::new();
@@ -259,4 +619,80 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_parse_ndjson_value() -> PResult<(), Error> {
+        let mut input = Stream::new("{\"a\": 1}\n");
+        let value = parse_ndjson(&mut input)?;
+
+        let mut expected = HashMap::new();
+        expected.insert("a".to_string(), JsonValue::Number(Num::Int(1)));
+        assert_eq!(value, Some(JsonValue::Object(expected)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_ndjson_blank_line() -> PResult<(), Error> {
+        let mut input = Stream::new("\n");
+        let value = parse_ndjson(&mut input)?;
+        assert_eq!(value, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_ndjson_incomplete() {
+        let mut input = Stream::new("{\"a\": 1}");
+        let err = parse_ndjson(&mut input).unwrap_err();
+        assert!(err.is_incomplete());
+    }
+
+    #[test]
+    fn test_parse_json_error_location() {
+        let input = "{\n  \"a\": 1,\n  \"b\" 2\n}";
+        let err = parse_json(input).unwrap_err();
+        assert_eq!(err.line, 3);
+        assert_eq!(err.column, 7);
+        assert_eq!(err.context, vec!["':'", "object key"]);
+        assert_eq!(
+            err.to_string(),
+            "error at line 3, col 7: expected ':' while parsing object key"
+        );
+    }
+
+    #[test]
+    fn test_to_string_round_trip() {
+        let input = r#"{"a": 1, "b": [1, -2.5, "x\ny"], "c": null, "d": true}"#;
+        let value = parse_json(input).unwrap();
+
+        let rendered = to_string(&value);
+        assert_eq!(parse_json(&rendered).unwrap(), value);
+    }
+
+    #[test]
+    fn test_to_string_number_formatting() {
+        assert_eq!(to_string(&JsonValue::Number(Num::Int(42))), "42");
+        assert_eq!(to_string(&JsonValue::Number(Num::UInt(u64::MAX))), u64::MAX.to_string());
+        assert_eq!(to_string(&JsonValue::Number(Num::Float(1.5))), "1.5");
+        assert_eq!(to_string(&JsonValue::Number(Num::Float(2.0))), "2.0");
+    }
+
+    #[test]
+    fn test_to_string_escapes_control_and_non_ascii() {
+        let value = JsonValue::String("a\"\\\n\u{1F600}".to_string());
+        assert_eq!(to_string(&value), r#""a\"\\\n\uD83D\uDE00""#);
+    }
+
+    #[test]
+    fn test_to_string_pretty_indents_nested_values() {
+        let mut obj = HashMap::new();
+        obj.insert("a".to_string(), JsonValue::Number(Num::Int(1)));
+        let value = JsonValue::Array(vec![JsonValue::Object(obj), JsonValue::Null]);
+
+        assert_eq!(
+            to_string_pretty(&value, 2),
+            "[\n  {\n    \"a\": 1\n  },\n  null\n]"
+        );
+    }
 }